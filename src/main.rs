@@ -62,7 +62,10 @@ fn discovery_only_main(args: &[String]) {
     use std::path::Path;
     use std::sync::Arc;
 
+    use std::str::FromStr;
+
     use arrow_client::output;
+    use arrow_client::output::OutputFormat;
     use arrow_client::scanner::discovery;
     use arrow_client::utils::logger::stderr::StderrLogger;
     use arrow_client::utils::logger::BoxLogger;
@@ -75,8 +78,12 @@ fn discovery_only_main(args: &[String]) {
     let mut mjpeg_paths_file = MJPEG_PATHS_FILE.to_string();
     let mut output_stdout = false;
     let mut output_file: Option<String> = None;
+    let mut output_format = OutputFormat::default();
     let mut verbose = false;
     let mut path_delay_ms: u64 = 50;
+    let mut insecure_tls = false;
+    let mut validate_timeout_ms: u64 = 3000;
+    let mut cache_file: Option<String> = None;
 
     let mut i = 0;
     while i < args.len() {
@@ -94,8 +101,20 @@ fn discovery_only_main(args: &[String]) {
             }
             "--output" => {
                 i += 1;
-                if i < args.len() && args[i] == "json" {
-                    i += 1;
+                if i < args.len() {
+                    match OutputFormat::from_str(&args[i]) {
+                        Ok(fmt) => {
+                            output_format = fmt;
+                            i += 1;
+                        }
+                        Err(e) => {
+                            eprintln!("ERROR: {}", e);
+                            discovery_only_usage(1);
+                        }
+                    }
+                } else {
+                    eprintln!("ERROR: --output requires a format");
+                    discovery_only_usage(1);
                 }
                 output_stdout = true;
             }
@@ -103,6 +122,10 @@ fn discovery_only_main(args: &[String]) {
                 verbose = true;
                 i += 1;
             }
+            "--insecure-tls" => {
+                insecure_tls = true;
+                i += 1;
+            }
             arg => {
                 if arg.starts_with("--output-file=") {
                     output_file = Some(arg["--output-file=".len()..].to_string());
@@ -114,6 +137,12 @@ fn discovery_only_main(args: &[String]) {
                     if let Ok(n) = arg["--path-delay-ms=".len()..].parse::<u64>() {
                         path_delay_ms = n;
                     }
+                } else if arg.starts_with("--validate-timeout-ms=") {
+                    if let Ok(n) = arg["--validate-timeout-ms=".len()..].parse::<u64>() {
+                        validate_timeout_ms = n;
+                    }
+                } else if arg.starts_with("--cache-file=") {
+                    cache_file = Some(arg["--cache-file=".len()..].to_string());
                 } else if arg == "--help" {
                     discovery_only_usage(0);
                 } else {
@@ -129,6 +158,11 @@ fn discovery_only_main(args: &[String]) {
         output_stdout = true;
     }
 
+    if cache_file.is_some() && output_format == OutputFormat::Ndjson {
+        eprintln!("ERROR: --cache-file is not supported with --output ndjson");
+        discovery_only_usage(1);
+    }
+
     let logger = BoxLogger::new(StderrLogger::new(false));
     let rtsp_paths = Arc::new(load_paths_file(&rtsp_paths_file).unwrap_or_default());
     let mjpeg_paths = Arc::new(load_paths_file(&mjpeg_paths_file).unwrap_or_default());
@@ -141,19 +175,79 @@ fn discovery_only_main(args: &[String]) {
     let discovery_whitelist = Arc::new(whitelist);
 
     let path_delay = std::time::Duration::from_millis(path_delay_ms);
-    let (scan_result, survey_opt) = match discovery::scan_network(
-        logger,
-        discovery_whitelist,
-        rtsp_paths.clone(),
-        mjpeg_paths.clone(),
-        verbose,
-        path_delay,
-    ) {
-        Ok(r) => r,
-        Err(e) => {
-            eprintln!("ERROR: discovery failed: {}", e);
-            std::process::exit(2);
+    let out_path = output_file.as_deref().map(Path::new);
+
+    let (scan_result, survey_opt) = if output_format == OutputFormat::Ndjson {
+        let mut writer = match output::ndjson::NdjsonWriter::create(output_stdout, out_path) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("ERROR: failed to open output: {}", e);
+                std::process::exit(3);
+            }
+        };
+        let validate_timeout = std::time::Duration::from_millis(validate_timeout_ms);
+        let mut on_service = |svc: &mut _| {
+            validate_one_service(svc, validate_timeout);
+
+            if let Err(e) = writer.write_service(svc) {
+                eprintln!("ERROR: failed to write output: {}", e);
+            }
+        };
+
+        let scan = discovery::scan_network(
+            logger,
+            discovery_whitelist,
+            rtsp_paths.clone(),
+            mjpeg_paths.clone(),
+            verbose,
+            path_delay,
+            insecure_tls,
+            Some(&mut on_service),
+        );
+
+        match scan {
+            Ok((scan_result, survey_opt)) => {
+                if verbose {
+                    let discovery_meta = output::DiscoveryMetadata {
+                        paths_rtsp_source: rtsp_paths_file,
+                        paths_rtsp_entries: (*rtsp_paths).clone(),
+                        paths_mjpeg_source: mjpeg_paths_file,
+                        paths_mjpeg_entries: (*mjpeg_paths).clone(),
+                        survey: survey_opt.clone().unwrap_or_default(),
+                    };
+                    if let Err(e) = writer.write_discovery(&discovery_meta) {
+                        eprintln!("ERROR: failed to write output: {}", e);
+                        std::process::exit(3);
+                    }
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("ERROR: discovery failed: {}", e);
+                std::process::exit(2);
+            }
         }
+    } else {
+        let (mut scan_result, survey_opt) = match discovery::scan_network(
+            logger,
+            discovery_whitelist,
+            rtsp_paths.clone(),
+            mjpeg_paths.clone(),
+            verbose,
+            path_delay,
+            insecure_tls,
+            None,
+        ) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("ERROR: discovery failed: {}", e);
+                std::process::exit(2);
+            }
+        };
+
+        validate_services(&mut scan_result, std::time::Duration::from_millis(validate_timeout_ms));
+
+        (scan_result, survey_opt)
     };
 
     let discovery_meta = if verbose {
@@ -168,13 +262,119 @@ fn discovery_only_main(args: &[String]) {
         None
     };
 
-    let out_path = output_file.as_deref().map(Path::new);
-    if let Err(e) = output::write_discovery_output(&scan_result, output_stdout, out_path, discovery_meta.as_ref()) {
+    if let Some(cache_path) = cache_file.as_deref().map(Path::new) {
+        if let Err(e) = write_cached_diff(&scan_result, cache_path, output_stdout, out_path) {
+            eprintln!("ERROR: failed to write output: {}", e);
+            std::process::exit(3);
+        }
+        return;
+    }
+
+    if let Err(e) = output::write_discovery_output(
+        &scan_result,
+        output_format,
+        output_stdout,
+        out_path,
+        discovery_meta.as_ref(),
+    ) {
         eprintln!("ERROR: failed to write output: {}", e);
         std::process::exit(3);
     }
 }
 
+/// Diff the current scan against the services cached from the last
+/// `--cache-file` run and write the diffed output (each service tagged
+/// `new`/`gone`/`unchanged`) to the requested sinks. The cache is
+/// overwritten with the current scan first, before the output is written,
+/// so the next run diffs against this one.
+#[cfg(feature = "discovery")]
+fn write_cached_diff(
+    scan_result: &arrow_client::scanner::result::ScanResult,
+    cache_path: &std::path::Path,
+    to_stdout: bool,
+    output_file: Option<&Path>,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    use arrow_client::output::cache;
+
+    let previous = cache::load(cache_path);
+    let current = cache::from_scan_result(scan_result);
+
+    cache::save_atomic(cache_path, &current)?;
+
+    let diffed = cache::diff(current, &previous);
+    let rendered = cache::render_diff(&diffed);
+
+    if to_stdout {
+        std::io::stdout().write_all(rendered.as_bytes())?;
+    }
+
+    if let Some(path) = output_file {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, rendered)?;
+    }
+
+    Ok(())
+}
+
+/// Run the media-validation pass over every discovered service, recording
+/// whether each one actually decodes. A no-op unless built with the
+/// `validate-media` feature.
+#[cfg(feature = "discovery")]
+fn validate_services(scan_result: &mut arrow_client::scanner::result::ScanResult, timeout: std::time::Duration) {
+    #[cfg(feature = "validate-media")]
+    {
+        for svc in scan_result.services_mut() {
+            validate_one_service(svc, timeout);
+        }
+    }
+
+    #[cfg(not(feature = "validate-media"))]
+    let _ = (scan_result, timeout);
+}
+
+/// Validate a single service in place, the same way `validate_services`
+/// does for a whole batch. Used by the NDJSON streaming path so each
+/// service reports `validated`/`codec`/`resolution` before it is written,
+/// instead of always reporting unvalidated the way the batched formats
+/// would if this pass were skipped.
+#[cfg(feature = "discovery")]
+fn validate_one_service(svc: &mut arrow_client::svc_table::Service, timeout: std::time::Duration) {
+    #[cfg(feature = "validate-media")]
+    {
+        use arrow_client::scanner::validate;
+
+        let Some(url) = service_probe_url(svc) else { return };
+        let result = validate::validate(&url, timeout);
+        svc.set_validation(result.validated, result.codec, result.resolution);
+    }
+
+    #[cfg(not(feature = "validate-media"))]
+    let _ = (svc, timeout);
+}
+
+/// Build the URL a validation pipeline should connect to for `svc`,
+/// reusing the scheme discovery already recorded in `Service::path` for
+/// TLS-reached services and falling back to the plaintext scheme implied
+/// by the service type otherwise.
+#[cfg(all(feature = "discovery", feature = "validate-media"))]
+fn service_probe_url(svc: &arrow_client::svc_table::Service) -> Option<String> {
+    use arrow_client::svc_table::ServiceType;
+
+    let address = svc.address()?;
+    let path = svc.path().unwrap_or("");
+
+    if let Some((scheme, rest)) = path.split_once("://") {
+        return Some(format!("{}://{}/{}", scheme, address, rest));
+    }
+
+    let scheme = if svc.service_type() == ServiceType::RTSP { "rtsp" } else { "http" };
+    Some(format!("{}://{}{}", scheme, address, path))
+}
+
 #[cfg(feature = "discovery")]
 fn load_paths_file(path: &str) -> std::io::Result<Vec<String>> {
     use std::io::BufRead;
@@ -200,13 +400,55 @@ fn discovery_only_usage(exit_code: i32) -> ! {
     println!();
     println!("OPTIONS:");
     println!("  -D iface       limit discovery to interface (repeatable)");
-    println!("  --output json  write JSON to stdout (default if no --output-file)");
+    println!("  --output <fmt> write output to stdout in <fmt> (json, yaml*, csv*, ndjson; default: json, *if built with the matching output-yaml/output-csv feature)");
+    println!("                 ndjson streams one JSON object per service as discovery progresses, instead of one document at the end");
     println!("  --output-file=<path>  write JSON to file");
     println!("  --rtsp-paths=<path>   path to RTSP paths file (default: /etc/arrow/rtsp-paths)");
     println!("  --mjpeg-paths=<path>  path to MJPEG paths file (default: /etc/arrow/mjpeg-paths)");
     println!("  --path-delay-ms=N     delay in ms between path probes per host (default: 50, 0=no throttle)");
     println!("  -v, --verbose  print path counts, host count, and path-check counts to stderr");
+    println!("  --insecure-tls accept self-signed certs when probing RTSPS/HTTPS-MJPEG (requires a tls-* feature)");
+    println!("  --validate-timeout-ms=N  time budget per service for the media-validation pass (default: 3000, requires the validate-media feature)");
+    println!("  --cache-file=<path>  diff this scan against the services cached at <path> from the previous run");
+    println!("                 each service is tagged new/gone/unchanged; overrides --output for this run and overwrites the cache atomically (not supported with --output ndjson)");
     println!("  --help         print this help");
     println!();
     std::process::exit(exit_code);
 }
+
+#[cfg(all(test, feature = "discovery", feature = "validate-media"))]
+mod tests {
+    use super::*;
+
+    use arrow_client::svc_table::{Service, ServiceType};
+
+    #[test]
+    fn service_probe_url_inserts_separator_for_tls_stored_path() {
+        let svc = Service::new(
+            ServiceType::RTSP,
+            None,
+            Some("203.0.113.5:322".parse().unwrap()),
+            Some("rtsps://live.sdp".to_string()),
+        );
+
+        assert_eq!(
+            service_probe_url(&svc).as_deref(),
+            Some("rtsps://203.0.113.5:322/live.sdp")
+        );
+    }
+
+    #[test]
+    fn service_probe_url_falls_back_to_plaintext_scheme_without_stored_scheme() {
+        let svc = Service::new(
+            ServiceType::RTSP,
+            None,
+            Some("203.0.113.5:554".parse().unwrap()),
+            Some("/live.sdp".to_string()),
+        );
+
+        assert_eq!(
+            service_probe_url(&svc).as_deref(),
+            Some("rtsp://203.0.113.5:554/live.sdp")
+        );
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Platform network-interface enumeration used to seed discovery scans.
+
+use std::collections::HashSet;
+use std::net::IpAddr;
+
+use crate::net::raw::ether::MacAddr;
+
+/// Return the (address, MAC) pairs of hosts reachable on the given
+/// interfaces (identified by name), or on every interface when
+/// `whitelist` is empty.
+///
+/// The interface/neighbour table lookup itself is platform-specific and
+/// lives below this function; callers only see the resulting address
+/// list.
+pub fn active_hosts(whitelist: &HashSet<String>) -> Vec<(IpAddr, MacAddr)> {
+    interfaces()
+        .into_iter()
+        .filter(|iface| whitelist.is_empty() || whitelist.contains(&iface.name))
+        .flat_map(|iface| iface.neighbours)
+        .collect()
+}
+
+struct Interface {
+    name: String,
+    neighbours: Vec<(IpAddr, MacAddr)>,
+}
+
+/// Parse a `/proc/net/arp`-style HW address column ("aa:bb:cc:dd:ee:ff").
+fn parse_mac(s: &str) -> Option<MacAddr> {
+    let mut octets = [0u8; 6];
+    let mut parts = s.split(':');
+
+    for octet in &mut octets {
+        *octet = u8::from_str_radix(parts.next()?, 16).ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(MacAddr::new(octets[0], octets[1], octets[2], octets[3], octets[4], octets[5]))
+}
+
+#[cfg(target_os = "linux")]
+fn interfaces() -> Vec<Interface> {
+    // Walk the kernel's resolved ARP table (`/proc/net/arp`) rather than
+    // sending ARP requests ourselves; any host the kernel has already
+    // resolved on an interface is a discovery candidate. This only finds
+    // hosts that have talked to us recently (or that were reached via a
+    // preceding ping sweep run by the operator) -- it is not an active
+    // neighbour probe.
+    let table = match std::fs::read_to_string("/proc/net/arp") {
+        Ok(contents) => contents,
+        Err(_) => {
+            eprintln!("WARNING: failed to read /proc/net/arp; discovery will find 0 hosts");
+            return Vec::new();
+        }
+    };
+
+    let mut by_device: std::collections::HashMap<String, Vec<(IpAddr, MacAddr)>> =
+        std::collections::HashMap::new();
+
+    for line in table.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        // IP address, HW type, Flags, HW address, Mask, Device.
+        let (ip, flags, hw_address, device) =
+            match (fields.first(), fields.get(2), fields.get(3), fields.get(5)) {
+                (Some(ip), Some(flags), Some(hw_address), Some(device)) => (ip, flags, hw_address, device),
+                _ => continue,
+            };
+
+        // Flags bit 0x2 (ATF_COMPLETE) marks a resolved entry; skip
+        // incomplete ones (flags == "0x0").
+        if *flags == "0x0" {
+            continue;
+        }
+
+        if let (Ok(addr), Some(mac)) = (ip.parse::<IpAddr>(), parse_mac(hw_address)) {
+            by_device.entry(device.to_string()).or_default().push((addr, mac));
+        }
+    }
+
+    by_device
+        .into_iter()
+        .map(|(name, neighbours)| Interface { name, neighbours })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn interfaces() -> Vec<Interface> {
+    // No ARP/NDP table walk is wired up for this platform yet; discovery
+    // will find 0 candidate hosts until one is added.
+    eprintln!("WARNING: neighbour discovery is not implemented on this platform; discovery will find 0 hosts");
+    Vec::new()
+}
@@ -0,0 +1,258 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Discovery cache, selected with `--cache-file=<path>`. Persists the
+//! services seen on the last run so a scan can report what changed
+//! (`new`/`gone`/`unchanged`), keyed by `(mac, address, path)`, instead of
+//! just the current snapshot.
+
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use json::JsonValue;
+
+use crate::scanner::result::ScanResult;
+
+/// Change status of one entry in a cache diff, relative to the previous
+/// run's cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    New,
+    Gone,
+    Unchanged,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::New => "new",
+            Status::Gone => "gone",
+            Status::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// A cached service record, as read back from (or about to be written to)
+/// the cache file. Only the key fields and `last_seen` are kept; the
+/// other fields of a scanned service (type, validation results, ...) are
+/// not cached and do not appear in the diff output.
+#[derive(Debug, Clone)]
+pub struct CachedService {
+    pub mac: String,
+    pub address: String,
+    pub path: String,
+    pub last_seen: i64,
+}
+
+/// A service plus its change status, ready to render.
+pub struct DiffedService {
+    pub service: CachedService,
+    pub status: Status,
+}
+
+fn key_of(svc: &CachedService) -> (&str, &str, &str) {
+    (svc.mac.as_str(), svc.address.as_str(), svc.path.as_str())
+}
+
+/// Load the previous run's cache. A missing or unparsable file just means
+/// this is the first run, so it returns an empty cache rather than an
+/// error.
+pub fn load(path: &Path) -> Vec<CachedService> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(_) => return Vec::new(),
+    };
+    let parsed = match json::parse(&text) {
+        Ok(parsed) => parsed,
+        Err(_) => return Vec::new(),
+    };
+
+    parsed["services"]
+        .members()
+        .filter_map(|svc| {
+            Some(CachedService {
+                mac: svc["mac"].as_str()?.to_string(),
+                address: svc["address"].as_str()?.to_string(),
+                path: svc["path"].as_str()?.to_string(),
+                last_seen: svc["last_seen"].as_i64().unwrap_or(0),
+            })
+        })
+        .collect()
+}
+
+/// Reduce the current scan to the fields the cache keys and diffs on.
+pub fn from_scan_result(result: &ScanResult) -> Vec<CachedService> {
+    let default_mac = crate::net::raw::ether::MacAddr::zero();
+    let default_address = std::net::SocketAddr::from(([0, 0, 0, 0], 0));
+    let last_seen = super::utc_timestamp_sec();
+
+    result
+        .services()
+        .map(|svc| CachedService {
+            mac: format!("{}", svc.mac().unwrap_or(default_mac)),
+            address: format!("{}", svc.address().unwrap_or(default_address)),
+            path: svc.path().unwrap_or("").to_string(),
+            last_seen,
+        })
+        .collect()
+}
+
+/// Diff `current` against `previous`, keyed by `(mac, address, path)`.
+/// Services present now are `new` or `unchanged`; services that were only
+/// in `previous` are re-emitted as `gone`, with their cached `last_seen`
+/// preserved rather than dropped.
+pub fn diff(current: Vec<CachedService>, previous: &[CachedService]) -> Vec<DiffedService> {
+    let previous_keys: HashSet<(&str, &str, &str)> = previous.iter().map(key_of).collect();
+    let current_keys: HashSet<(&str, &str, &str)> = current.iter().map(key_of).collect();
+
+    let mut diffed: Vec<DiffedService> = current
+        .into_iter()
+        .map(|svc| {
+            let status = if previous_keys.contains(&key_of(&svc)) {
+                Status::Unchanged
+            } else {
+                Status::New
+            };
+            DiffedService { service: svc, status }
+        })
+        .collect();
+
+    diffed.extend(previous.iter().filter(|svc| !current_keys.contains(&key_of(svc))).map(|svc| DiffedService {
+        service: svc.clone(),
+        status: Status::Gone,
+    }));
+
+    diffed
+}
+
+/// Render a diffed service list as a JSON document. Each service carries
+/// `id`, `mac`, `address`, `path`, `last_seen` and `status` only; the other
+/// fields of the regular discovery output are not included.
+pub fn render_diff(diffed: &[DiffedService]) -> String {
+    let services: Vec<JsonValue> = diffed
+        .iter()
+        .enumerate()
+        .map(|(i, d)| {
+            object! {
+                "id" => (i + 1) as u16,
+                "mac" => d.service.mac.as_str(),
+                "address" => d.service.address.as_str(),
+                "path" => d.service.path.as_str(),
+                "last_seen" => d.service.last_seen,
+                "status" => d.status.as_str()
+            }
+        })
+        .collect();
+
+    let root = object! {
+        "services" => services
+    };
+
+    root.dump()
+}
+
+/// Write `services` to `path` atomically: serialize to a temp file next to
+/// `path`, then rename over the destination, so a crash mid-write never
+/// leaves a corrupt or partial cache behind.
+pub fn save_atomic(path: &Path, services: &[CachedService]) -> io::Result<()> {
+    let entries: Vec<JsonValue> = services
+        .iter()
+        .map(|svc| {
+            object! {
+                "mac" => svc.mac.as_str(),
+                "address" => svc.address.as_str(),
+                "path" => svc.path.as_str(),
+                "last_seen" => svc.last_seen
+            }
+        })
+        .collect();
+
+    let root = object! {
+        "services" => entries
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = Path::new(&tmp_path);
+
+    std::fs::write(tmp_path, root.dump())?;
+    std::fs::rename(tmp_path, path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn svc(mac: &str, address: &str, path: &str, last_seen: i64) -> CachedService {
+        CachedService {
+            mac: mac.to_string(),
+            address: address.to_string(),
+            path: path.to_string(),
+            last_seen,
+        }
+    }
+
+    #[test]
+    fn key_of_ignores_last_seen() {
+        let a = svc("00:11:22:33:44:55", "1.2.3.4:554", "/live.sdp", 1);
+        let b = svc("00:11:22:33:44:55", "1.2.3.4:554", "/live.sdp", 2);
+
+        assert_eq!(key_of(&a), key_of(&b));
+    }
+
+    #[test]
+    fn diff_classifies_new_gone_and_unchanged() {
+        let previous = vec![
+            svc("00:00:00:00:00:01", "10.0.0.1:554", "/a", 100),
+            svc("00:00:00:00:00:02", "10.0.0.2:554", "/b", 100),
+        ];
+        let current = vec![
+            svc("00:00:00:00:00:01", "10.0.0.1:554", "/a", 200),
+            svc("00:00:00:00:00:03", "10.0.0.3:554", "/c", 200),
+        ];
+
+        let diffed = diff(current, &previous);
+
+        let status_of = |mac: &str| {
+            diffed
+                .iter()
+                .find(|d| d.service.mac == mac)
+                .map(|d| d.status)
+                .unwrap()
+        };
+
+        assert_eq!(status_of("00:00:00:00:00:01"), Status::Unchanged);
+        assert_eq!(status_of("00:00:00:00:00:02"), Status::Gone);
+        assert_eq!(status_of("00:00:00:00:00:03"), Status::New);
+    }
+
+    #[test]
+    fn diff_preserves_last_seen_for_gone_services() {
+        let previous = vec![svc("00:00:00:00:00:01", "10.0.0.1:554", "/a", 100)];
+        let current = Vec::new();
+
+        let diffed = diff(current, &previous);
+
+        assert_eq!(diffed.len(), 1);
+        assert_eq!(diffed[0].status, Status::Gone);
+        assert_eq!(diffed[0].service.last_seen, 100);
+    }
+}
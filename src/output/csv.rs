@@ -0,0 +1,133 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CSV formatter, enabled by the `output-csv` feature. Flattens one row per
+//! service for spreadsheet consumption; the discovery survey block has no
+//! tabular shape and is dropped.
+
+#![cfg(feature = "output-csv")]
+
+use super::model::{ScanOutputModel, ServiceRecord};
+
+const HEADER: &str = "id,svc_type,mac,address,path,last_seen,validated,codec,resolution";
+
+/// Render the model as CSV, one row per service (`id,svc_type,mac,address,
+/// path,last_seen,validated,codec,resolution`). The discovery block is
+/// omitted.
+pub fn render(model: &ScanOutputModel) -> String {
+    let mut out = String::new();
+    out.push_str(HEADER);
+    out.push('\n');
+
+    for svc in &model.services {
+        out.push_str(&service_to_csv_row(svc));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn service_to_csv_row(svc: &ServiceRecord) -> String {
+    format!(
+        "{},{},{},{},{},{},{},{},{}",
+        svc.id,
+        svc.svc_type,
+        csv_field(&svc.mac),
+        csv_field(&svc.address),
+        csv_field(&svc.path),
+        svc.last_seen,
+        svc.validated,
+        csv_field(svc.codec.as_deref().unwrap_or("")),
+        csv_field(svc.resolution.as_deref().unwrap_or(""))
+    )
+}
+
+/// Quote a field per RFC 4180 if it contains a comma, quote, or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_passes_plain_values_through() {
+        assert_eq!(csv_field("rtsp"), "rtsp");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_field_quotes_commas() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+    }
+
+    #[test]
+    fn csv_field_quotes_and_escapes_embedded_quotes() {
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn csv_field_quotes_embedded_newlines() {
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+
+    #[test]
+    fn service_to_csv_row_includes_validation_columns() {
+        let svc = ServiceRecord {
+            id: 1,
+            svc_type: 1,
+            mac: "00:00:00:00:00:00".to_string(),
+            address: "203.0.113.5:554".to_string(),
+            path: "/live.sdp".to_string(),
+            static_svc: false,
+            last_seen: 0,
+            active: true,
+            validated: true,
+            codec: Some("h264".to_string()),
+            resolution: Some("1920x1080".to_string()),
+        };
+
+        assert_eq!(
+            service_to_csv_row(&svc),
+            "1,1,00:00:00:00:00:00,203.0.113.5:554,/live.sdp,0,true,h264,1920x1080"
+        );
+    }
+
+    #[test]
+    fn service_to_csv_row_leaves_codec_and_resolution_blank_when_unvalidated() {
+        let svc = ServiceRecord {
+            id: 2,
+            svc_type: 2,
+            mac: "00:00:00:00:00:00".to_string(),
+            address: "203.0.113.5:80".to_string(),
+            path: "/snapshot.cgi".to_string(),
+            static_svc: false,
+            last_seen: 0,
+            active: true,
+            validated: false,
+            codec: None,
+            resolution: None,
+        };
+
+        assert_eq!(
+            service_to_csv_row(&svc),
+            "2,2,00:00:00:00:00:00,203.0.113.5:80,/snapshot.cgi,0,false,,"
+        );
+    }
+}
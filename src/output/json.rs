@@ -0,0 +1,103 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON formatter. Reproduces the full nested `services`/`discovery` tree
+//! that the service table JSON already uses, for compatibility.
+
+use json::JsonValue;
+
+use super::model::{DiscoveryBlock, ScanOutputModel, ServiceRecord};
+
+/// Render the model as a JSON document.
+pub fn render(model: &ScanOutputModel) -> String {
+    let services: Vec<JsonValue> = model.services.iter().map(service_to_json).collect();
+
+    let mut root = object! {
+        "services" => services
+    };
+
+    if let Some(discovery) = &model.discovery {
+        root["discovery"] = discovery_to_json(discovery);
+    }
+
+    root.dump()
+}
+
+/// Render a single service as one self-contained JSON line, for NDJSON
+/// streaming.
+pub(crate) fn render_service_line(svc: &ServiceRecord) -> String {
+    service_to_json(svc).dump()
+}
+
+/// Render the trailing NDJSON line carrying the discovery metadata block.
+pub(crate) fn render_discovery_line(discovery: &DiscoveryBlock) -> String {
+    let root = object! {
+        "discovery" => discovery_to_json(discovery)
+    };
+
+    root.dump()
+}
+
+fn service_to_json(svc: &ServiceRecord) -> JsonValue {
+    let mut value = object! {
+        "id" => svc.id,
+        "svc_type" => svc.svc_type,
+        "mac" => svc.mac.as_str(),
+        "address" => svc.address.as_str(),
+        "path" => svc.path.as_str(),
+        "static_svc" => svc.static_svc,
+        "last_seen" => svc.last_seen,
+        "active" => svc.active,
+        "validated" => svc.validated
+    };
+
+    value["codec"] = svc.codec.as_deref().map_or(JsonValue::Null, JsonValue::from);
+    value["resolution"] = svc.resolution.as_deref().map_or(JsonValue::Null, JsonValue::from);
+
+    value
+}
+
+fn discovery_to_json(d: &DiscoveryBlock) -> JsonValue {
+    let paths = object! {
+        "rtsp" => object! {
+            "source" => d.rtsp_paths.source.as_str(),
+            "entries" => JsonValue::Array(d.rtsp_paths.entries.iter().map(|s| JsonValue::String(s.clone())).collect())
+        },
+        "mjpeg" => object! {
+            "source" => d.mjpeg_paths.source.as_str(),
+            "entries" => JsonValue::Array(d.mjpeg_paths.entries.iter().map(|s| JsonValue::String(s.clone())).collect())
+        }
+    };
+
+    let mut survey_obj = JsonValue::new_object();
+    for (addr, entries) in &d.survey {
+        let arr = JsonValue::Array(
+            entries
+                .iter()
+                .map(|e| {
+                    object! {
+                        "path" => e.path.as_str(),
+                        "result" => e.result.as_str()
+                    }
+                })
+                .collect(),
+        );
+        survey_obj[addr.as_str()] = arr;
+    }
+
+    object! {
+        "paths" => paths,
+        "survey" => survey_obj
+    }
+}
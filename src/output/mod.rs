@@ -0,0 +1,145 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Discovery output: write scan results to stdout and/or a file in a
+//! selectable format. JSON is always available and matches the service
+//! table JSON shape for compatibility; YAML and CSV are gated behind the
+//! `output-yaml` and `output-csv` features so minimal builds don't pull in
+//! formatters nobody uses.
+
+#![cfg(feature = "discovery")]
+
+pub mod cache;
+mod csv;
+mod json;
+mod model;
+pub mod ndjson;
+mod yaml;
+
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::scanner::discovery::SurveyData;
+use crate::scanner::result::ScanResult;
+
+use self::model::ScanOutputModel;
+
+/// Metadata for the discovery block (paths sources + survey). When present, included as "discovery" in the output of formats that have one (not CSV).
+pub struct DiscoveryMetadata {
+    pub paths_rtsp_source: String,
+    pub paths_rtsp_entries: Vec<String>,
+    pub paths_mjpeg_source: String,
+    pub paths_mjpeg_entries: Vec<String>,
+    pub survey: SurveyData,
+}
+
+/// Output format selectable via `--output <fmt>` on `--discovery-only`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Json,
+    #[cfg(feature = "output-yaml")]
+    Yaml,
+    #[cfg(feature = "output-csv")]
+    Csv,
+    /// One JSON object per service, written incrementally as the scan
+    /// progresses via `ndjson::NdjsonWriter` rather than through
+    /// `write_discovery_output`.
+    Ndjson,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Json
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownOutputFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(OutputFormat::Json),
+            #[cfg(feature = "output-yaml")]
+            "yaml" => Ok(OutputFormat::Yaml),
+            #[cfg(feature = "output-csv")]
+            "csv" => Ok(OutputFormat::Csv),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(UnknownOutputFormat(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when `--output <fmt>` names a format that is unknown or
+/// not compiled in.
+#[derive(Debug)]
+pub struct UnknownOutputFormat(String);
+
+impl fmt::Display for UnknownOutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown or disabled output format: \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for UnknownOutputFormat {}
+
+pub(crate) fn utc_timestamp_sec() -> i64 {
+    time::now_utc().to_timespec().sec
+}
+
+/// Write discovery result to the given sinks, encoded with `format`.
+/// - If `to_stdout` is true, writes the encoded output to stdout.
+/// - If `output_file` is `Some(path)`, writes the same output to that file.
+/// - If `discovery` is `Some`, the output includes a "discovery" block (paths + survey), when the format supports one.
+///
+/// `OutputFormat::Ndjson` is not supported here: it is written
+/// incrementally as the scan runs, via `ndjson::NdjsonWriter`.
+pub fn write_discovery_output(
+    result: &ScanResult,
+    format: OutputFormat,
+    to_stdout: bool,
+    output_file: Option<&Path>,
+    discovery: Option<&DiscoveryMetadata>,
+) -> io::Result<()> {
+    let model = ScanOutputModel::build(result, discovery);
+
+    let rendered = match format {
+        OutputFormat::Json => json::render(&model),
+        #[cfg(feature = "output-yaml")]
+        OutputFormat::Yaml => yaml::render(&model),
+        #[cfg(feature = "output-csv")]
+        OutputFormat::Csv => csv::render(&model),
+        OutputFormat::Ndjson => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "ndjson is written incrementally during the scan, not via write_discovery_output",
+            ))
+        }
+    };
+
+    if to_stdout {
+        io::stdout().write_all(rendered.as_bytes())?;
+    }
+
+    if let Some(path) = output_file {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, rendered)?;
+    }
+
+    Ok(())
+}
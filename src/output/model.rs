@@ -0,0 +1,153 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Intermediate, format-agnostic representation of a discovery scan. Each
+//! output formatter (JSON, YAML, CSV, ...) renders this model rather than
+//! touching `ScanResult`/`SurveyData` directly, so adding a format never
+//! requires re-walking the scanner types.
+
+use crate::net::raw::ether::MacAddr;
+use crate::scanner::discovery::SurveyData;
+use crate::scanner::result::ScanResult;
+use crate::svc_table::Service;
+
+/// A single discovered service, flattened to the fields every formatter
+/// needs.
+pub struct ServiceRecord {
+    pub id: u16,
+    pub svc_type: u16,
+    pub mac: String,
+    pub address: String,
+    pub path: String,
+    pub static_svc: bool,
+    pub last_seen: i64,
+    pub active: bool,
+    /// Whether a `validate-media` pass confirmed this service decodes.
+    /// Always `false` when that feature is disabled or validation hasn't
+    /// run yet.
+    pub validated: bool,
+    pub codec: Option<String>,
+    pub resolution: Option<String>,
+}
+
+/// Source file + entries for one path list (RTSP or MJPEG).
+pub struct PathList {
+    pub source: String,
+    pub entries: Vec<String>,
+}
+
+/// Result of probing a single path on a single host during the survey.
+pub struct SurveyEntry {
+    pub path: String,
+    pub result: String,
+}
+
+/// The "discovery" metadata block: path lists plus the per-host survey.
+pub struct DiscoveryBlock {
+    pub rtsp_paths: PathList,
+    pub mjpeg_paths: PathList,
+    pub survey: Vec<(String, Vec<SurveyEntry>)>,
+}
+
+/// Format-agnostic view of a scan result. Built once from `ScanResult` and
+/// `DiscoveryMetadata`, then handed to whichever formatter was selected.
+pub struct ScanOutputModel {
+    pub services: Vec<ServiceRecord>,
+    pub discovery: Option<DiscoveryBlock>,
+}
+
+impl ScanOutputModel {
+    /// Build the model from a scan result and optional discovery metadata.
+    pub fn build(result: &ScanResult, discovery: Option<&super::DiscoveryMetadata>) -> Self {
+        let default_mac = MacAddr::zero();
+        let default_address = std::net::SocketAddr::from(([0, 0, 0, 0], 0));
+        let last_seen = super::utc_timestamp_sec();
+
+        let services = result
+            .services()
+            .enumerate()
+            .map(|(i, svc)| service_record((i + 1) as u16, svc, last_seen, default_mac, default_address))
+            .collect();
+
+        let discovery = discovery.map(discovery_block);
+
+        ScanOutputModel { services, discovery }
+    }
+}
+
+/// Build a single service record, defaulting fields the service itself
+/// doesn't carry. Exposed beyond this module so the NDJSON writer can build
+/// one record per service as it streams, instead of waiting for a full
+/// `ScanOutputModel`.
+pub(crate) fn service_record_for_stream(id: u16, svc: &Service, last_seen: i64) -> ServiceRecord {
+    service_record(id, svc, last_seen, MacAddr::zero(), std::net::SocketAddr::from(([0, 0, 0, 0], 0)))
+}
+
+fn service_record(
+    id: u16,
+    svc: &Service,
+    last_seen: i64,
+    default_mac: MacAddr,
+    default_address: std::net::SocketAddr,
+) -> ServiceRecord {
+    let svc_type = svc.service_type();
+    let mac = svc.mac().unwrap_or(default_mac);
+    let address = svc.address().unwrap_or(default_address);
+    let path = svc.path().unwrap_or("");
+
+    ServiceRecord {
+        id,
+        svc_type: svc_type.code(),
+        mac: format!("{}", mac),
+        address: format!("{}", address),
+        path: path.to_string(),
+        static_svc: false,
+        last_seen,
+        active: true,
+        validated: svc.validated(),
+        codec: svc.codec().map(|c| c.to_string()),
+        resolution: svc.resolution().map(|(w, h)| format!("{}x{}", w, h)),
+    }
+}
+
+pub(crate) fn discovery_block(d: &super::DiscoveryMetadata) -> DiscoveryBlock {
+    DiscoveryBlock {
+        rtsp_paths: PathList {
+            source: d.paths_rtsp_source.clone(),
+            entries: d.paths_rtsp_entries.clone(),
+        },
+        mjpeg_paths: PathList {
+            source: d.paths_mjpeg_source.clone(),
+            entries: d.paths_mjpeg_entries.clone(),
+        },
+        survey: survey_entries(&d.survey),
+    }
+}
+
+fn survey_entries(survey: &SurveyData) -> Vec<(String, Vec<SurveyEntry>)> {
+    survey
+        .iter()
+        .map(|(addr, entries)| {
+            let entries = entries
+                .iter()
+                .map(|(path, result)| SurveyEntry {
+                    path: path.clone(),
+                    result: result.clone(),
+                })
+                .collect();
+
+            (addr.clone(), entries)
+        })
+        .collect()
+}
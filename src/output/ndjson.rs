@@ -0,0 +1,91 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Incremental NDJSON output, selected with `--output ndjson`. Unlike the
+//! other formatters, this one is not rendered from a finished
+//! `ScanOutputModel` — it is fed one `Service` at a time as the scan
+//! discovers them, writing and flushing a line immediately so a killed
+//! process loses at most the in-flight line. Once the scan has finished,
+//! `write_discovery` can add a final line carrying the discovery metadata
+//! block; `--discovery-only` does so only in verbose mode.
+
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+use crate::svc_table::Service;
+
+use super::json;
+use super::model::{self, DiscoveryBlock};
+use super::{utc_timestamp_sec, DiscoveryMetadata};
+
+/// Writes one JSON object per discovered service to stdout and/or a file,
+/// flushing after every line.
+pub struct NdjsonWriter {
+    to_stdout: bool,
+    file: Option<File>,
+    next_id: u16,
+}
+
+impl NdjsonWriter {
+    /// Open the sinks for a streaming run.
+    pub fn create(to_stdout: bool, path: Option<&Path>) -> io::Result<Self> {
+        let file = match path {
+            Some(path) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                Some(File::create(path)?)
+            }
+            None => None,
+        };
+
+        Ok(NdjsonWriter {
+            to_stdout,
+            file,
+            next_id: 1,
+        })
+    }
+
+    /// Serialize a newly confirmed service as one JSON line and flush it.
+    pub fn write_service(&mut self, svc: &Service) -> io::Result<()> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let record = model::service_record_for_stream(id, svc, utc_timestamp_sec());
+        self.write_line(&json::render_service_line(&record))
+    }
+
+    /// Write the trailing line carrying the discovery metadata block.
+    pub fn write_discovery(&mut self, discovery: &DiscoveryMetadata) -> io::Result<()> {
+        let block: DiscoveryBlock = model::discovery_block(discovery);
+        self.write_line(&json::render_discovery_line(&block))
+    }
+
+    fn write_line(&mut self, line: &str) -> io::Result<()> {
+        if self.to_stdout {
+            let mut stdout = io::stdout();
+            writeln!(stdout, "{}", line)?;
+            stdout.flush()?;
+        }
+
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+
+        Ok(())
+    }
+}
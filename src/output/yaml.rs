@@ -0,0 +1,180 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! YAML formatter, enabled by the `output-yaml` feature. Reproduces the
+//! same nested `services`/`discovery` tree as the JSON formatter so the two
+//! are interchangeable for downstream config pipelines.
+
+#![cfg(feature = "output-yaml")]
+
+use super::model::{DiscoveryBlock, ScanOutputModel, ServiceRecord};
+
+/// Render the model as a YAML document.
+pub fn render(model: &ScanOutputModel) -> String {
+    let mut out = String::new();
+
+    out.push_str("services:\n");
+    if model.services.is_empty() {
+        out.push_str("  []\n");
+    } else {
+        for svc in &model.services {
+            out.push_str(&service_to_yaml(svc));
+        }
+    }
+
+    if let Some(discovery) = &model.discovery {
+        out.push_str("discovery:\n");
+        out.push_str(&discovery_to_yaml(discovery));
+    }
+
+    out
+}
+
+fn service_to_yaml(svc: &ServiceRecord) -> String {
+    format!(
+        "  - id: {}\n    svc_type: {}\n    mac: {}\n    address: {}\n    path: {}\n    static_svc: {}\n    last_seen: {}\n    active: {}\n    validated: {}\n    codec: {}\n    resolution: {}\n",
+        svc.id,
+        svc.svc_type,
+        yaml_scalar(&svc.mac),
+        yaml_scalar(&svc.address),
+        yaml_scalar(&svc.path),
+        svc.static_svc,
+        svc.last_seen,
+        svc.active,
+        svc.validated,
+        svc.codec.as_deref().map_or("null".to_string(), yaml_scalar),
+        svc.resolution.as_deref().map_or("null".to_string(), yaml_scalar)
+    )
+}
+
+fn discovery_to_yaml(d: &DiscoveryBlock) -> String {
+    let mut out = String::new();
+
+    out.push_str("  paths:\n");
+    out.push_str("    rtsp:\n");
+    out.push_str(&path_list_to_yaml(&d.rtsp_paths.source, &d.rtsp_paths.entries, "      "));
+    out.push_str("    mjpeg:\n");
+    out.push_str(&path_list_to_yaml(&d.mjpeg_paths.source, &d.mjpeg_paths.entries, "      "));
+
+    out.push_str("  survey:\n");
+    if d.survey.is_empty() {
+        out.push_str("    {}\n");
+    } else {
+        for (addr, entries) in &d.survey {
+            out.push_str(&format!("    {}:\n", yaml_scalar(addr)));
+            if entries.is_empty() {
+                out.push_str("      []\n");
+            } else {
+                for e in entries {
+                    out.push_str(&format!(
+                        "      - path: {}\n        result: {}\n",
+                        yaml_scalar(&e.path),
+                        yaml_scalar(&e.result)
+                    ));
+                }
+            }
+        }
+    }
+
+    out
+}
+
+fn path_list_to_yaml(source: &str, entries: &[String], indent: &str) -> String {
+    let mut out = format!("{}source: {}\n{}entries:\n", indent, yaml_scalar(source), indent);
+    if entries.is_empty() {
+        out.push_str(&format!("{}  []\n", indent));
+    } else {
+        for entry in entries {
+            out.push_str(&format!("{}  - {}\n", indent, yaml_scalar(entry)));
+        }
+    }
+    out
+}
+
+/// Quote a scalar if it contains characters that would otherwise change its
+/// meaning in YAML (colons, leading/trailing whitespace, empty string, ...)
+/// or if it would be re-parsed as a bool/null/number instead of a string
+/// (e.g. "no", "123").
+fn yaml_scalar(s: &str) -> String {
+    let needs_quoting = s.is_empty()
+        || s.contains(':')
+        || s.contains('#')
+        || s.contains('"')
+        || s.contains('\\')
+        || s.contains('\n')
+        || s.starts_with(' ')
+        || s.ends_with(' ')
+        || is_ambiguous_scalar(s);
+
+    if needs_quoting {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Whether `s` would parse back as a YAML 1.1 bool/null/number rather than
+/// a string if left unquoted.
+fn is_ambiguous_scalar(s: &str) -> bool {
+    matches!(
+        s.to_ascii_lowercase().as_str(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "~"
+    ) || s.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_scalar_passes_plain_values_through() {
+        assert_eq!(yaml_scalar("rtsp"), "rtsp");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_empty_string() {
+        assert_eq!(yaml_scalar(""), "\"\"");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_values_with_colons_and_hashes() {
+        assert_eq!(yaml_scalar("10.0.0.1:554"), "\"10.0.0.1:554\"");
+        assert_eq!(yaml_scalar("a#b"), "\"a#b\"");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_leading_or_trailing_whitespace() {
+        assert_eq!(yaml_scalar(" leading"), "\" leading\"");
+        assert_eq!(yaml_scalar("trailing "), "\"trailing \"");
+    }
+
+    #[test]
+    fn yaml_scalar_escapes_backslashes_and_quotes() {
+        assert_eq!(yaml_scalar("a\\b\"c"), "\"a\\\\b\\\"c\"");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_bool_and_null_lookalikes() {
+        assert_eq!(yaml_scalar("true"), "\"true\"");
+        assert_eq!(yaml_scalar("no"), "\"no\"");
+        assert_eq!(yaml_scalar("Null"), "\"Null\"");
+        assert_eq!(yaml_scalar("~"), "\"~\"");
+    }
+
+    #[test]
+    fn yaml_scalar_quotes_numeric_lookalikes() {
+        assert_eq!(yaml_scalar("123"), "\"123\"");
+        assert_eq!(yaml_scalar("1.5"), "\"1.5\"");
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! One-shot network discovery: probe candidate hosts for known RTSP/MJPEG
+//! paths and collect whatever answers as a `ScanResult`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::net::raw::ether::MacAddr;
+use crate::scanner::result::ScanResult;
+use crate::svc_table::{Service, ServiceType};
+use crate::utils::logger::BoxLogger;
+use crate::utils::logger::Logger;
+
+const RTSP_PORT: u16 = 554;
+const MJPEG_PORT: u16 = 80;
+
+#[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
+const RTSPS_PORT: u16 = 322;
+#[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
+const HTTPS_MJPEG_PORT: u16 = 443;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Per-host, per-path probe results, keyed by host address string. Only
+/// populated when discovery is run in verbose mode.
+pub type SurveyData = BTreeMap<String, Vec<(String, String)>>;
+
+/// Error returned when a discovery run cannot be completed at all (as
+/// opposed to individual paths simply not answering, which is recorded in
+/// the survey instead of failing the whole scan).
+#[derive(Debug)]
+pub struct DiscoveryError(String);
+
+impl fmt::Display for DiscoveryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "discovery error: {}", self.0)
+    }
+}
+
+impl std::error::Error for DiscoveryError {}
+
+/// Run a one-time discovery scan.
+///
+/// `whitelist` restricts the scan to the named interfaces (empty means all
+/// interfaces). `rtsp_paths`/`mjpeg_paths` are tried against every
+/// candidate host found on those interfaces, `path_delay` apart, both in
+/// plaintext and (when built with a `tls-*` feature) over TLS on the
+/// RTSPS/HTTPS-MJPEG ports; `insecure_tls` accepts self-signed certs, which
+/// is the common case for IP cameras. When `verbose` is set, the per-path
+/// survey is returned alongside the result.
+///
+/// `on_service`, when given, is invoked with every confirmed service the
+/// moment it is confirmed, and the service is *not* also accumulated into
+/// the returned `ScanResult` — callers that want live progress (e.g. NDJSON
+/// streaming) hook in here instead of waiting for the whole scan to finish,
+/// and get a memory-bounded scan in exchange for building their own output
+/// incrementally. Pass `None` to get the usual fully-collected
+/// `ScanResult` instead.
+pub fn scan_network(
+    mut logger: BoxLogger,
+    whitelist: Arc<HashSet<String>>,
+    rtsp_paths: Arc<Vec<String>>,
+    mjpeg_paths: Arc<Vec<String>>,
+    verbose: bool,
+    path_delay: Duration,
+    insecure_tls: bool,
+    mut on_service: Option<&mut dyn FnMut(&mut Service)>,
+) -> Result<(ScanResult, Option<SurveyData>), DiscoveryError> {
+    #[cfg(not(any(feature = "tls-native-roots", feature = "tls-webpki-roots")))]
+    let _ = insecure_tls;
+
+    let hosts = discover_hosts(&whitelist);
+
+    logger.info(&format!("discovery: {} candidate host(s)", hosts.len()));
+
+    let mut result = ScanResult::new();
+    let mut survey = SurveyData::new();
+
+    for (host, mac) in &hosts {
+        let host = *host;
+        let mac = *mac;
+        let mut entries = Vec::new();
+
+        for path in rtsp_paths.iter() {
+            let outcome = probe_plain(host, RTSP_PORT, path);
+            record_probe(
+                &mut result,
+                &mut entries,
+                &mut on_service,
+                ServiceType::RTSP,
+                host,
+                mac,
+                RTSP_PORT,
+                path,
+                "rtsp",
+                outcome,
+                verbose,
+            );
+            std::thread::sleep(path_delay);
+        }
+
+        for path in mjpeg_paths.iter() {
+            let outcome = probe_plain(host, MJPEG_PORT, path);
+            record_probe(
+                &mut result,
+                &mut entries,
+                &mut on_service,
+                ServiceType::MJPEG,
+                host,
+                mac,
+                MJPEG_PORT,
+                path,
+                "http",
+                outcome,
+                verbose,
+            );
+            std::thread::sleep(path_delay);
+        }
+
+        #[cfg(any(feature = "tls-native-roots", feature = "tls-webpki-roots"))]
+        {
+            for path in rtsp_paths.iter() {
+                let outcome = crate::scanner::tls::probe_tls(host, RTSPS_PORT, insecure_tls);
+                record_probe(
+                    &mut result,
+                    &mut entries,
+                    &mut on_service,
+                    ServiceType::RTSP,
+                    host,
+                    mac,
+                    RTSPS_PORT,
+                    path,
+                    "rtsps",
+                    outcome,
+                    verbose,
+                );
+                std::thread::sleep(path_delay);
+            }
+
+            for path in mjpeg_paths.iter() {
+                let outcome = crate::scanner::tls::probe_tls(host, HTTPS_MJPEG_PORT, insecure_tls);
+                record_probe(
+                    &mut result,
+                    &mut entries,
+                    &mut on_service,
+                    ServiceType::MJPEG,
+                    host,
+                    mac,
+                    HTTPS_MJPEG_PORT,
+                    path,
+                    "https",
+                    outcome,
+                    verbose,
+                );
+                std::thread::sleep(path_delay);
+            }
+        }
+
+        if verbose && !entries.is_empty() {
+            survey.insert(host.to_string(), entries);
+        }
+    }
+
+    Ok((result, if verbose { Some(survey) } else { None }))
+}
+
+/// Probe a single plaintext path. Returns `true` if the host accepted a TCP
+/// connection on the given port (the most we can say without a protocol
+/// handshake, but enough to tell live cameras from dead addresses).
+fn probe_plain(host: IpAddr, port: u16, _path: &str) -> bool {
+    TcpStream::connect_timeout(&SocketAddr::new(host, port), CONNECT_TIMEOUT).is_ok()
+}
+
+#[allow(clippy::too_many_arguments)]
+fn record_probe(
+    result: &mut ScanResult,
+    entries: &mut Vec<(String, String)>,
+    on_service: &mut Option<&mut dyn FnMut(&mut Service)>,
+    service_type: ServiceType,
+    host: IpAddr,
+    mac: MacAddr,
+    port: u16,
+    path: &str,
+    scheme: &str,
+    ok: bool,
+    verbose: bool,
+) {
+    let address = SocketAddr::new(host, port);
+
+    if ok {
+        // TLS-reached paths keep their scheme in `Service::path` (e.g.
+        // "rtsps://live.sdp") so the scheme survives into every output
+        // format without widening the service record itself.
+        let stored_path = match scheme {
+            "rtsp" | "http" => path.to_string(),
+            tls_scheme => format!("{}://{}", tls_scheme, path.trim_start_matches('/')),
+        };
+        let mut service = Service::new(service_type, Some(mac), Some(address), Some(stored_path));
+
+        if let Some(cb) = on_service.as_mut() {
+            cb(&mut service);
+        } else {
+            result.push(service);
+        }
+    }
+
+    if verbose {
+        let url = format!("{}://{}{}", scheme, address, path);
+        entries.push((url, if ok { "ok".to_string() } else { "unreachable".to_string() }));
+    }
+}
+
+/// Enumerate candidate hosts to probe, restricted to `whitelist` interfaces
+/// when non-empty. Interface-based neighbour discovery lives in the raw
+/// networking layer; this just asks it for the current set of live
+/// addresses on the selected interfaces.
+fn discover_hosts(whitelist: &HashSet<String>) -> Vec<(IpAddr, MacAddr)> {
+    crate::net::raw::devices::active_hosts(whitelist)
+}
@@ -0,0 +1,52 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The result of a network discovery scan.
+
+use crate::svc_table::Service;
+
+/// All services found during a single discovery run.
+#[derive(Debug, Clone, Default)]
+pub struct ScanResult {
+    services: Vec<Service>,
+}
+
+impl ScanResult {
+    pub fn new() -> Self {
+        ScanResult { services: Vec::new() }
+    }
+
+    /// Record a newly discovered service.
+    pub fn push(&mut self, service: Service) {
+        self.services.push(service);
+    }
+
+    /// Iterate over all discovered services.
+    pub fn services(&self) -> impl Iterator<Item = &Service> {
+        self.services.iter()
+    }
+
+    /// Iterate mutably, e.g. to attach validation results after the scan.
+    pub fn services_mut(&mut self) -> impl Iterator<Item = &mut Service> {
+        self.services.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.services.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS handshake probing for RTSPS/HTTPS-MJPEG endpoints, used by the
+//! discovery scanner. The trust store is selected at compile time: the
+//! `tls-native-roots` feature uses the OS trust store via
+//! `rustls-native-certs`, `tls-webpki-roots` uses the bundled Mozilla root
+//! set. `--insecure-tls` accepts any certificate, which is the common case
+//! for IP cameras that ship with a self-signed cert.
+
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, ClientConnection, Error as TlsError, RootCertStore, ServerName};
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(800);
+
+/// Attempt a TLS handshake with `host:port`. Returns `true` if the
+/// handshake completes, under the trust policy implied by `insecure`.
+pub fn probe_tls(host: IpAddr, port: u16, insecure: bool) -> bool {
+    let addr = SocketAddr::new(host, port);
+
+    let stream = match TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+    let _ = stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(HANDSHAKE_TIMEOUT));
+
+    let server_name = match ServerName::try_from(host.to_string().as_str()) {
+        Ok(name) => name,
+        Err(_) => return false,
+    };
+
+    let config = Arc::new(client_config(insecure));
+    let mut conn = match ClientConnection::new(config, server_name) {
+        Ok(conn) => conn,
+        Err(_) => return false,
+    };
+
+    let mut stream_ref = &stream;
+    let mut tls_stream = rustls::Stream::new(&mut conn, &mut stream_ref);
+
+    // The handshake completes lazily on first I/O; a flush is enough to
+    // force it without sending any protocol bytes of our own.
+    tls_stream.flush().is_ok()
+}
+
+fn client_config(insecure: bool) -> ClientConfig {
+    let builder = ClientConfig::builder().with_safe_defaults();
+
+    if insecure {
+        return builder
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert))
+            .with_no_client_auth();
+    }
+
+    builder.with_root_certificates(trust_roots()).with_no_client_auth()
+}
+
+#[cfg(feature = "tls-native-roots")]
+fn trust_roots() -> RootCertStore {
+    let mut roots = RootCertStore::empty();
+    if let Ok(certs) = rustls_native_certs::load_native_certs() {
+        for cert in certs {
+            let _ = roots.add(&Certificate(cert.0));
+        }
+    }
+    roots
+}
+
+#[cfg(all(feature = "tls-webpki-roots", not(feature = "tls-native-roots")))]
+fn trust_roots() -> RootCertStore {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(ta.subject, ta.spki, ta.name_constraints)
+    }));
+    roots
+}
+
+/// Accepts any certificate chain, for cameras with self-signed certs
+/// (`--insecure-tls`).
+struct AcceptAnyCert;
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
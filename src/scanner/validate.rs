@@ -0,0 +1,134 @@
+// Copyright 2017 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Media-validation pass, enabled by the `validate-media` feature. A
+//! discovered path only tells us a camera answered; this builds a
+//! short-lived `rtspsrc`/`souphttpsrc` -> `decodebin` -> `fakesink`
+//! pipeline for it and checks whether at least one frame actually decodes,
+//! so dead or misconfigured cameras aren't reported as live.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use gst::prelude::*;
+
+/// Outcome of validating one discovered path. A pipeline that never builds
+/// or never produces a decoded frame within the timeout degrades to the
+/// all-`None`/`false` default rather than failing the caller.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationResult {
+    pub validated: bool,
+    pub codec: Option<String>,
+    pub resolution: Option<(u32, u32)>,
+    pub framerate: Option<(i32, i32)>,
+}
+
+/// Validate `url` (an `rtsp(s)://` or `http(s)://` MJPEG URL) for up to
+/// `timeout`. Never panics or propagates a pipeline error: any failure
+/// degrades to "unvalidated".
+pub fn validate(url: &str, timeout: Duration) -> ValidationResult {
+    try_validate(url, timeout).unwrap_or_default()
+}
+
+fn try_validate(url: &str, timeout: Duration) -> Option<ValidationResult> {
+    gst::init().ok()?;
+
+    let src_factory = if url.starts_with("rtsp") { "rtspsrc" } else { "souphttpsrc" };
+
+    let pipeline = gst::Pipeline::new(None);
+    let src = gst::ElementFactory::make(src_factory).property("location", url).build().ok()?;
+    let decodebin = gst::ElementFactory::make("decodebin").build().ok()?;
+    let sink = gst::ElementFactory::make("fakesink").build().ok()?;
+
+    pipeline.add_many(&[&src, &decodebin, &sink]).ok()?;
+
+    // `rtspsrc`'s src pad is a "sometimes" pad that only appears once the
+    // RTSP session negotiates, so it can't be linked statically the way
+    // `souphttpsrc`'s can; hook `pad-added` the same way decodebin's src
+    // pad is handled below.
+    if src.static_pad("src").is_some() {
+        src.link(&decodebin).ok()?;
+    } else {
+        let decodebin_cb = decodebin.clone();
+        src.connect_pad_added(move |_, pad| {
+            if let Some(sink_pad) = decodebin_cb.static_pad("sink") {
+                if !sink_pad.is_linked() {
+                    let _ = pad.link(&sink_pad);
+                }
+            }
+        });
+    }
+
+    let caps_info: Arc<Mutex<ValidationResult>> = Arc::new(Mutex::new(ValidationResult::default()));
+    let caps_info_cb = caps_info.clone();
+    let sink_cb = sink.clone();
+
+    decodebin.connect_pad_added(move |_, pad| {
+        if let Some(caps) = pad.current_caps() {
+            if let Some(structure) = caps.structure(0) {
+                let mut info = caps_info_cb.lock().unwrap();
+                info.codec = Some(structure.name().to_string());
+                if let (Ok(width), Ok(height)) = (structure.get::<i32>("width"), structure.get::<i32>("height")) {
+                    info.resolution = Some((width as u32, height as u32));
+                }
+                if let Ok(framerate) = structure.get::<gst::Fraction>("framerate") {
+                    info.framerate = Some((framerate.numer(), framerate.denom()));
+                }
+            }
+        }
+
+        if let Some(sink_pad) = sink_cb.static_pad("sink") {
+            if !sink_pad.is_linked() {
+                let _ = pad.link(&sink_pad);
+            }
+        }
+    });
+
+    pipeline.set_state(gst::State::Playing).ok()?;
+
+    let bus = pipeline.bus()?;
+    let deadline = Instant::now() + timeout;
+    let mut frame_seen = false;
+
+    while Instant::now() < deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let poll_window = remaining.min(Duration::from_millis(100));
+
+        if let Some(msg) = bus.timed_pop(gst::ClockTime::from_mseconds(poll_window.as_millis() as u64)) {
+            match msg.view() {
+                gst::MessageView::AsyncDone(_) => {
+                    frame_seen = true;
+                    break;
+                }
+                gst::MessageView::Eos(_) => {
+                    frame_seen = true;
+                    break;
+                }
+                gst::MessageView::Error(_) => break,
+                _ => {}
+            }
+        }
+    }
+
+    let _ = pipeline.set_state(gst::State::Null);
+
+    let mut result = caps_info.lock().unwrap().clone();
+    result.validated = frame_seen && result.codec.is_some();
+
+    if !result.validated {
+        return Some(ValidationResult::default());
+    }
+
+    Some(result)
+}
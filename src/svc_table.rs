@@ -0,0 +1,102 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Service table types shared between the live client and the
+//! discovery-only scanner.
+
+use std::net::SocketAddr;
+
+use crate::net::raw::ether::MacAddr;
+
+/// Numeric service type code, as used on the wire and in the service table
+/// JSON (`svc_type`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceType(u16);
+
+impl ServiceType {
+    pub const CONTROL_PROTOCOL: ServiceType = ServiceType(0);
+    pub const RTSP: ServiceType = ServiceType(1);
+    pub const MJPEG: ServiceType = ServiceType(2);
+
+    /// The numeric code for this service type.
+    pub fn code(&self) -> u16 {
+        self.0
+    }
+}
+
+/// A single discovered (or statically configured) service.
+#[derive(Debug, Clone)]
+pub struct Service {
+    service_type: ServiceType,
+    mac: Option<MacAddr>,
+    address: Option<SocketAddr>,
+    path: Option<String>,
+    validated: bool,
+    codec: Option<String>,
+    resolution: Option<(u32, u32)>,
+}
+
+impl Service {
+    /// Create a new service record. Media validation fields start out
+    /// unset; see `set_validation`.
+    pub fn new(service_type: ServiceType, mac: Option<MacAddr>, address: Option<SocketAddr>, path: Option<String>) -> Self {
+        Service {
+            service_type,
+            mac,
+            address,
+            path,
+            validated: false,
+            codec: None,
+            resolution: None,
+        }
+    }
+
+    pub fn service_type(&self) -> ServiceType {
+        self.service_type
+    }
+
+    pub fn mac(&self) -> Option<MacAddr> {
+        self.mac
+    }
+
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.address
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Record the outcome of a media-validation pass (see
+    /// `scanner::validate`) for this service.
+    pub fn set_validation(&mut self, validated: bool, codec: Option<String>, resolution: Option<(u32, u32)>) {
+        self.validated = validated;
+        self.codec = codec;
+        self.resolution = resolution;
+    }
+
+    /// Whether a validation pass confirmed this service actually decodes.
+    /// `false` both when validation hasn't run and when it ran and failed.
+    pub fn validated(&self) -> bool {
+        self.validated
+    }
+
+    pub fn codec(&self) -> Option<&str> {
+        self.codec.as_deref()
+    }
+
+    pub fn resolution(&self) -> Option<(u32, u32)> {
+        self.resolution
+    }
+}
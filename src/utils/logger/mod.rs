@@ -0,0 +1,68 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Minimal pluggable logging used by the discovery scanner.
+
+pub mod stderr;
+
+/// Log severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+/// A sink for scanner log messages.
+pub trait Logger: Send {
+    fn log(&mut self, severity: Severity, message: &str);
+
+    fn debug(&mut self, message: &str) {
+        self.log(Severity::Debug, message);
+    }
+
+    fn info(&mut self, message: &str) {
+        self.log(Severity::Info, message);
+    }
+
+    fn warn(&mut self, message: &str) {
+        self.log(Severity::Warn, message);
+    }
+
+    fn error(&mut self, message: &str) {
+        self.log(Severity::Error, message);
+    }
+}
+
+/// A boxed handle to a `Logger` implementation, so the scanner can take
+/// any logger by value. It is not `Clone`.
+pub struct BoxLogger {
+    inner: Box<dyn Logger>,
+}
+
+impl BoxLogger {
+    pub fn new<L>(logger: L) -> Self
+    where
+        L: Logger + 'static,
+    {
+        BoxLogger { inner: Box::new(logger) }
+    }
+}
+
+impl Logger for BoxLogger {
+    fn log(&mut self, severity: Severity, message: &str) {
+        self.inner.log(severity, message);
+    }
+}
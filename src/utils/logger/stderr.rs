@@ -0,0 +1,38 @@
+// Copyright 2015 click2stream, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `Logger` implementation that writes to stderr.
+
+use super::{Logger, Severity};
+
+/// Logs every message to stderr, optionally including debug-level messages.
+pub struct StderrLogger {
+    debug: bool,
+}
+
+impl StderrLogger {
+    pub fn new(debug: bool) -> Self {
+        StderrLogger { debug }
+    }
+}
+
+impl Logger for StderrLogger {
+    fn log(&mut self, severity: Severity, message: &str) {
+        if severity == Severity::Debug && !self.debug {
+            return;
+        }
+
+        eprintln!("[{:?}] {}", severity, message);
+    }
+}